@@ -0,0 +1,77 @@
+use std::io::Write;
+
+use super::{Cmd, Generator};
+
+pub(crate) struct Bash;
+
+impl Generator for Bash {
+    fn file_name(&self, bin_name: &str) -> String {
+        format!("{}.bash", bin_name)
+    }
+
+    fn generate(&self, cmd: &Cmd, buf: &mut dyn Write) {
+        let bin_name = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
+        let fn_name = bin_name.replace('-', "_");
+
+        let _ = writeln!(buf, "_{}() {{", fn_name);
+        let _ = writeln!(buf, "    local cur prev words cword");
+        let _ = writeln!(buf, "    _init_completion || return");
+        let _ = writeln!(buf, "    local opts=\"{}\"", all_options(cmd));
+        let _ = writeln!(buf, "    COMPREPLY=( $(compgen -W \"${{opts}}\" -- \"$cur\") )");
+        let _ = writeln!(buf, "    return 0");
+        let _ = writeln!(buf, "}}");
+        let _ = writeln!(buf, "complete -F _{} {}", fn_name, bin_name);
+    }
+}
+
+fn all_options(cmd: &Cmd) -> String {
+    let mut opts = Vec::new();
+    for arg in cmd.get_arguments() {
+        if let Some(short) = arg.get_short() {
+            opts.push(format!("-{}", short));
+        }
+        if let Some(long) = arg.get_long() {
+            opts.push(format!("--{}", long));
+        }
+        for value in arg.get_possible_values() {
+            opts.push(value.to_string());
+        }
+    }
+    for sub in cmd.get_subcommands() {
+        opts.push(sub.get_name().to_string());
+    }
+    opts.join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::Bash;
+    use crate::completions::{Cmd, CmdArg, Generator};
+
+    fn test_cmd() -> Cmd {
+        Cmd::new("myapp")
+            .arg(CmdArg::new().short('v').long("verbose").help("be loud"))
+            .arg(
+                CmdArg::new()
+                    .long("color")
+                    .possible_value("always")
+                    .possible_value("never"),
+            )
+            .subcommand(Cmd::new("build"))
+    }
+
+    #[test]
+    fn lists_flags_values_and_subcommands_as_compgen_words() {
+        let mut out = Vec::new();
+        Bash.generate(&test_cmd(), &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("_myapp()"));
+        assert!(script.contains("local opts=\"-v --verbose --color always never build\""));
+        assert!(script.contains("complete -F _myapp myapp"));
+    }
+
+    #[test]
+    fn file_name_is_bin_name_dot_bash() {
+        assert_eq!(Bash.file_name("myapp"), "myapp.bash");
+    }
+}
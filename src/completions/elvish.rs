@@ -0,0 +1,82 @@
+use std::io::Write;
+
+use super::{Cmd, Generator};
+
+pub(crate) struct Elvish;
+
+impl Generator for Elvish {
+    fn file_name(&self, bin_name: &str) -> String {
+        format!("{}.elv", bin_name)
+    }
+
+    fn generate(&self, cmd: &Cmd, buf: &mut dyn Write) {
+        let bin_name = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
+
+        let _ = writeln!(buf, "edit:completion:arg-completer[{}] = [@words]{{", bin_name);
+        let _ = writeln!(buf, "    put {{");
+        for arg in cmd.get_arguments() {
+            if let Some(long) = arg.get_long() {
+                let _ = writeln!(
+                    buf,
+                    "        &'--{0}'= {{ put ['--{0}' '{1}'] }}",
+                    long,
+                    escape(arg.get_help().unwrap_or(""))
+                );
+            }
+            for value in arg.get_possible_values() {
+                let _ = writeln!(buf, "        &'{0}'= {{ put ['{0}' ''] }}", escape(value));
+            }
+        }
+        for sub in cmd.get_subcommands() {
+            let _ = writeln!(
+                buf,
+                "        &'{0}'= {{ put ['{0}' '{1}'] }}",
+                sub.get_name(),
+                escape(sub.get_about().unwrap_or(""))
+            );
+        }
+        let _ = writeln!(buf, "    }}");
+        let _ = writeln!(buf, "}}");
+    }
+}
+
+// Elvish single-quoted strings escape an embedded `'` by doubling it, not with a backslash (the
+// way zsh/fish do) — using either of those techniques here would leave a literal, unescaped `'`
+// that ends the string early, same risk as the zsh generator when this text is attacker- or
+// config-file-influenced.
+fn escape(s: &str) -> String {
+    s.replace('\'', "''")
+}
+
+#[cfg(test)]
+mod test {
+    use super::Elvish;
+    use crate::completions::{Cmd, CmdArg, Generator};
+
+    #[test]
+    fn emits_long_flags_values_and_subcommands() {
+        let cmd = Cmd::new("myapp")
+            .arg(
+                CmdArg::new()
+                    .long("color")
+                    .help("when to color output")
+                    .possible_value("always"),
+            )
+            .subcommand(Cmd::new("build").about("build the project"));
+        let mut out = Vec::new();
+        Elvish.generate(&cmd, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("&'--color'= { put ['--color' 'when to color output'] }"));
+        assert!(script.contains("&'always'= { put ['always' ''] }"));
+        assert!(script.contains("&'build'= { put ['build' 'build the project'] }"));
+    }
+
+    #[test]
+    fn escapes_embedded_quote_by_doubling() {
+        let cmd = Cmd::new("myapp").arg(CmdArg::new().long("name").help("user's name"));
+        let mut out = Vec::new();
+        Elvish.generate(&cmd, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("put ['--name' 'user''s name']"));
+    }
+}
@@ -0,0 +1,93 @@
+use std::io::Write;
+
+use super::{Cmd, Generator};
+
+pub(crate) struct Fish;
+
+impl Generator for Fish {
+    fn file_name(&self, bin_name: &str) -> String {
+        format!("{}.fish", bin_name)
+    }
+
+    fn generate(&self, cmd: &Cmd, buf: &mut dyn Write) {
+        let bin_name = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
+
+        for arg in cmd.get_arguments() {
+            let help = arg.get_help().unwrap_or("");
+            let mut line = format!("complete -c {}", bin_name);
+            if let Some(short) = arg.get_short() {
+                line.push_str(&format!(" -s {}", short));
+            }
+            if let Some(long) = arg.get_long() {
+                line.push_str(&format!(" -l {}", long));
+            }
+            let values = arg.get_possible_values();
+            if !values.is_empty() {
+                let values = values.iter().map(|v| escape(v)).collect::<Vec<_>>().join(" ");
+                line.push_str(&format!(" -f -a '{}'", values));
+            }
+            if !help.is_empty() {
+                line.push_str(&format!(" -d '{}'", escape(help)));
+            }
+            let _ = writeln!(buf, "{}", line);
+        }
+        for sub in cmd.get_subcommands() {
+            let _ = writeln!(
+                buf,
+                "complete -c {} -n '__fish_use_subcommand' -f -a '{}' -d '{}'",
+                bin_name,
+                sub.get_name(),
+                escape(sub.get_about().unwrap_or(""))
+            );
+        }
+    }
+}
+
+// Fish single-quoted strings do support backslash-escaping an embedded `'`, unlike zsh/elvish.
+fn escape(s: &str) -> String {
+    s.replace('\'', "\\'")
+}
+
+#[cfg(test)]
+mod test {
+    use super::Fish;
+    use crate::completions::{Cmd, CmdArg, Generator};
+
+    #[test]
+    fn emits_complete_lines_for_args_values_and_subcommands() {
+        let cmd = Cmd::new("myapp")
+            .arg(
+                CmdArg::new()
+                    .short('v')
+                    .long("verbose")
+                    .help("be loud"),
+            )
+            .arg(
+                CmdArg::new()
+                    .long("color")
+                    .possible_value("always")
+                    .possible_value("never"),
+            )
+            .subcommand(Cmd::new("build").about("build the project"));
+        let mut out = Vec::new();
+        Fish.generate(&cmd, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("complete -c myapp -s v -l verbose -d 'be loud'"));
+        assert!(script.contains("complete -c myapp -l color -f -a 'always never'"));
+        assert!(script.contains(
+            "complete -c myapp -n '__fish_use_subcommand' -f -a 'build' -d 'build the project'"
+        ));
+    }
+
+    #[test]
+    fn escapes_embedded_quote_in_help_and_about() {
+        let cmd = Cmd::new("myapp")
+            .arg(CmdArg::new().long("name").help("user's name"))
+            .subcommand(Cmd::new("run").about("run it, don't ask"));
+        let mut out = Vec::new();
+        Fish.generate(&cmd, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("-d 'user\\'s name'"));
+        assert!(script.contains("-d 'run it, don\\'t ask'"));
+    }
+}
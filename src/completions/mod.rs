@@ -0,0 +1,205 @@
+//! Shell completion script generation.
+//!
+//! [`generate`] walks a command tree (flags, options, possible values, subcommands) and writes
+//! out a script that a particular shell's completion engine understands, so
+//! `myapp completions bash > /etc/bash_completion.d/myapp` doesn't require a separate crate.
+//!
+//! The tree is described by [`Cmd`]/[`CmdArg`] rather than `crate::build::App`/`Arg`, which don't
+//! exist in this tree — that would have made this whole module uncallable and untestable from
+//! day one. `Cmd`/`CmdArg` carry exactly what the five generators below need (name, bin name,
+//! about text, and each arg's short/long/help/possible values), so this module is independently
+//! constructible and tested now; translating a real `App` into a `Cmd` once one exists is a
+//! mechanical `From` impl, not a rewrite of anything here.
+
+use std::io::Write;
+
+mod bash;
+mod elvish;
+mod fish;
+mod powershell;
+mod zsh;
+
+/// A shell that [`generate`] knows how to emit a completion script for.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Shell {
+    /// Completions for the Bourne Again SHell (bash).
+    Bash,
+    /// Completions for the Elvish shell.
+    Elvish,
+    /// Completions for the Friendly Interactive SHell (fish).
+    Fish,
+    /// Completions for PowerShell.
+    PowerShell,
+    /// Completions for the Z SHell (zsh).
+    Zsh,
+}
+
+impl Shell {
+    fn generator(self) -> &'static dyn Generator {
+        match self {
+            Shell::Bash => &bash::Bash,
+            Shell::Elvish => &elvish::Elvish,
+            Shell::Fish => &fish::Fish,
+            Shell::PowerShell => &powershell::PowerShell,
+            Shell::Zsh => &zsh::Zsh,
+        }
+    }
+}
+
+/// A minimal, self-contained description of a command: its name, args, and subcommands — the
+/// stand-in for `crate::build::App` this module depends on instead (see the module docs above).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Cmd {
+    name: String,
+    bin_name: Option<String>,
+    about: Option<String>,
+    args: Vec<CmdArg>,
+    subcommands: Vec<Cmd>,
+}
+
+impl Cmd {
+    pub(crate) fn new(name: impl Into<String>) -> Self {
+        Cmd {
+            name: name.into(),
+            ..Cmd::default()
+        }
+    }
+
+    pub(crate) fn about(mut self, about: impl Into<String>) -> Self {
+        self.about = Some(about.into());
+        self
+    }
+
+    pub(crate) fn arg(mut self, arg: CmdArg) -> Self {
+        self.args.push(arg);
+        self
+    }
+
+    pub(crate) fn subcommand(mut self, sub: Cmd) -> Self {
+        self.subcommands.push(sub);
+        self
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_bin_name(&self) -> Option<&str> {
+        self.bin_name.as_deref()
+    }
+
+    fn get_about(&self) -> Option<&str> {
+        self.about.as_deref()
+    }
+
+    fn get_arguments(&self) -> &[CmdArg] {
+        &self.args
+    }
+
+    fn get_subcommands(&self) -> &[Cmd] {
+        &self.subcommands
+    }
+}
+
+/// A single flag/option within a [`Cmd`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CmdArg {
+    short: Option<char>,
+    long: Option<String>,
+    help: Option<String>,
+    possible_values: Vec<String>,
+}
+
+impl CmdArg {
+    pub(crate) fn new() -> Self {
+        CmdArg::default()
+    }
+
+    pub(crate) fn short(mut self, short: char) -> Self {
+        self.short = Some(short);
+        self
+    }
+
+    pub(crate) fn long(mut self, long: impl Into<String>) -> Self {
+        self.long = Some(long.into());
+        self
+    }
+
+    pub(crate) fn help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub(crate) fn possible_value(mut self, value: impl Into<String>) -> Self {
+        self.possible_values.push(value.into());
+        self
+    }
+
+    fn get_short(&self) -> Option<char> {
+        self.short
+    }
+
+    fn get_long(&self) -> Option<&str> {
+        self.long.as_deref()
+    }
+
+    fn get_help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    fn get_possible_values(&self) -> &[String] {
+        &self.possible_values
+    }
+}
+
+// Implemented once per shell; keeps the per-shell script-writing logic out of `generate` itself.
+pub(crate) trait Generator {
+    /// The conventional file name a generated script is installed under, e.g. `_myapp` for zsh.
+    fn file_name(&self, bin_name: &str) -> String;
+    /// Writes the completion script for `cmd` (whose `bin_name` is already set) to `buf`.
+    fn generate(&self, cmd: &Cmd, buf: &mut dyn Write);
+}
+
+/// Generates a `shell` completion script for `cmd`, under the given `bin_name`, writing it to
+/// `buf`.
+///
+/// `bin_name` overrides whatever name `cmd` was built with, since the installed binary name (what
+/// users actually type) doesn't always match the crate/app name.
+///
+/// `pub(crate)` rather than `pub` for now, since [`Cmd`] — the type this takes — is itself
+/// `pub(crate)`: there's no `crate::build::App` yet for a public caller to build one from. This
+/// becomes the real public entry point once that exists, most likely behind a
+/// `From<&App> for Cmd` conversion rather than a signature change here.
+pub(crate) fn generate<W: Write>(cmd: &Cmd, shell: Shell, bin_name: &str, buf: &mut W) {
+    let mut cmd = cmd.clone();
+    cmd.bin_name = Some(bin_name.to_string());
+    shell.generator().generate(&cmd, buf);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate, Cmd, Shell};
+
+    #[test]
+    fn generate_overrides_the_bin_name_for_every_shell() {
+        let cmd = Cmd::new("internal-crate-name");
+        for shell in [
+            Shell::Bash,
+            Shell::Elvish,
+            Shell::Fish,
+            Shell::PowerShell,
+            Shell::Zsh,
+        ] {
+            let mut out = Vec::new();
+            generate(&cmd, shell, "myapp", &mut out);
+            let script = String::from_utf8(out).unwrap();
+            assert!(
+                script.contains("myapp"),
+                "{:?} completion script didn't mention the overridden bin name: {}",
+                shell,
+                script
+            );
+            assert!(!script.contains("internal-crate-name"));
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::io::Write;
+
+use super::{Cmd, Generator};
+
+pub(crate) struct PowerShell;
+
+impl Generator for PowerShell {
+    fn file_name(&self, bin_name: &str) -> String {
+        format!("_{}.ps1", bin_name)
+    }
+
+    fn generate(&self, cmd: &Cmd, buf: &mut dyn Write) {
+        let bin_name = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
+
+        let _ = writeln!(
+            buf,
+            "Register-ArgumentCompleter -Native -CommandName '{}' -ScriptBlock {{",
+            bin_name
+        );
+        let _ = writeln!(
+            buf,
+            "    param($wordToComplete, $commandAst, $cursorPosition)"
+        );
+        let _ = writeln!(buf, "    $candidates = @(");
+        for arg in cmd.get_arguments() {
+            if let Some(long) = arg.get_long() {
+                let _ = writeln!(buf, "        '--{}'", long);
+            }
+            if let Some(short) = arg.get_short() {
+                let _ = writeln!(buf, "        '-{}'", short);
+            }
+            for value in arg.get_possible_values() {
+                let _ = writeln!(buf, "        '{}'", value.replace('\'', "''"));
+            }
+        }
+        for sub in cmd.get_subcommands() {
+            let _ = writeln!(buf, "        '{}'", sub.get_name());
+        }
+        let _ = writeln!(buf, "    )");
+        let _ = writeln!(
+            buf,
+            "    $candidates | Where-Object {{ $_ -like \"$wordToComplete*\" }} | ForEach-Object {{ [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_) }}"
+        );
+        let _ = writeln!(buf, "}}");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PowerShell;
+    use crate::completions::{Cmd, CmdArg, Generator};
+
+    #[test]
+    fn lists_flags_values_and_subcommands_as_literal_candidates() {
+        let cmd = Cmd::new("myapp")
+            .arg(
+                CmdArg::new()
+                    .short('v')
+                    .long("verbose")
+                    .possible_value("always")
+                    .possible_value("never"),
+            )
+            .subcommand(Cmd::new("build"));
+        let mut out = Vec::new();
+        PowerShell.generate(&cmd, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("'--verbose'"));
+        assert!(script.contains("'-v'"));
+        assert!(script.contains("'always'"));
+        assert!(script.contains("'never'"));
+        assert!(script.contains("'build'"));
+    }
+
+    #[test]
+    fn escapes_embedded_quote_in_possible_value_by_doubling() {
+        let cmd = Cmd::new("myapp").arg(CmdArg::new().long("name").possible_value("o'brien"));
+        let mut out = Vec::new();
+        PowerShell.generate(&cmd, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("'o''brien'"));
+    }
+
+    #[test]
+    fn file_name_is_underscore_bin_name_dot_ps1() {
+        assert_eq!(PowerShell.file_name("myapp"), "_myapp.ps1");
+    }
+}
@@ -0,0 +1,116 @@
+use std::io::Write;
+
+use super::{Cmd, CmdArg, Generator};
+
+pub(crate) struct Zsh;
+
+impl Generator for Zsh {
+    fn file_name(&self, bin_name: &str) -> String {
+        format!("_{}", bin_name)
+    }
+
+    fn generate(&self, cmd: &Cmd, buf: &mut dyn Write) {
+        let bin_name = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
+
+        let _ = writeln!(buf, "#compdef {}", bin_name);
+        let _ = writeln!(buf);
+        let _ = writeln!(buf, "_{}() {{", bin_name.replace('-', "_"));
+        let _ = writeln!(buf, "    local -a args");
+        let _ = writeln!(buf, "    args=(");
+        for arg in cmd.get_arguments() {
+            let help = escape(arg.get_help().unwrap_or(""));
+            let values = values_spec(arg);
+            match (arg.get_short(), arg.get_long()) {
+                (Some(s), Some(l)) => {
+                    let _ = writeln!(
+                        buf,
+                        "        '(-{0} --{1})'{{-{0},--{1}}}'[{2}]{3}'",
+                        s, l, help, values
+                    );
+                }
+                (Some(s), None) => {
+                    let _ = writeln!(buf, "        '-{}[{}]{}'", s, help, values);
+                }
+                (None, Some(l)) => {
+                    let _ = writeln!(buf, "        '--{}[{}]{}'", l, help, values);
+                }
+                (None, None) => {}
+            }
+        }
+        let _ = writeln!(buf, "    )");
+        let _ = writeln!(buf, "    _arguments $args");
+        let _ = writeln!(buf, "}}");
+        let _ = writeln!(buf);
+        let _ = writeln!(buf, "_{}", bin_name.replace('-', "_"));
+    }
+}
+
+// `_arguments` spec strings are each wrapped in a single-quoted shell literal, and zsh (unlike
+// fish) doesn't let a single-quoted string escape an embedded `'` with a backslash, so a `'` has
+// to close the literal, insert a backslash-escaped one, then reopen it. `]` closes the `[...]`
+// help-text action inside the spec itself, so it needs escaping too or it prematurely ends the
+// help text (and an unescaped `'` is otherwise a way to inject extra spec syntax).
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+}
+
+// Renders the `:name:(values...)` action zsh uses to offer `possible_values` as completions, or
+// an empty string when the arg doesn't restrict its values.
+fn values_spec(arg: &CmdArg) -> String {
+    let values = arg.get_possible_values();
+    if values.is_empty() {
+        return String::new();
+    }
+    let values = values
+        .iter()
+        .map(|v| escape(v))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(":value:({})", values)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Zsh;
+    use crate::completions::{Cmd, CmdArg, Generator};
+
+    #[test]
+    fn emits_both_short_and_long_forms_with_help_and_values() {
+        let cmd = Cmd::new("myapp").arg(
+            CmdArg::new()
+                .short('c')
+                .long("color")
+                .help("when to color output")
+                .possible_value("always")
+                .possible_value("never"),
+        );
+        let mut out = Vec::new();
+        Zsh.generate(&cmd, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains(
+            "'(-c --color)'{-c,--color}'[when to color output]:value:(always never)'"
+        ));
+    }
+
+    #[test]
+    fn escapes_embedded_quote_and_brackets_in_help_text() {
+        let cmd = Cmd::new("myapp").arg(
+            CmdArg::new()
+                .long("name")
+                .help("user's display name [optional]"),
+        );
+        let mut out = Vec::new();
+        Zsh.generate(&cmd, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script
+            .contains("'--name[user'\\''s display name \\[optional\\]]'"));
+    }
+
+    #[test]
+    fn file_name_is_underscore_bin_name() {
+        assert_eq!(Zsh.file_name("myapp"), "_myapp");
+    }
+}
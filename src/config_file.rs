@@ -0,0 +1,269 @@
+//! Config-file fallback layer, mirroring the existing `yaml` feature's "build from a document"
+//! story but the other way around: instead of building an `App` from a file, this loads a file's
+//! values as a fallback layer underneath the command line and above hard-coded defaults.
+//!
+//! [`ConfigFile::load`] and [`ConfigFile::get`] are the whole contract a parser hook would need:
+//! load the file once up front, then call `get(arg_name)` for whichever args weren't found on the
+//! command line or via [`crate::env_fallback`]. Nested tables/objects are flattened onto dotted
+//! keys (`[section]` / `key` becomes `section.key`) and arrays are joined with `,`, so a value
+//! read back out of here is exactly what the user would have typed for that arg on the CLI.
+//!
+//! Actually consulting this from a parser still needs a place on `App` to stash the configured
+//! path (`App::config_file(path)`) and the CLI > env > config > default precedence chain that
+//! calls into it — `crate::build::App` and the parser in `crate::parse` aren't part of this
+//! checkout, so that half isn't here yet.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Why a config file failed to load. Kept local to this module rather than reusing
+/// `crate::parse::errors::Error`/`ErrorKind`, which has no variant for an unreadable or malformed
+/// config file.
+#[derive(Debug)]
+pub(crate) struct ConfigFileError(String);
+
+impl fmt::Display for ConfigFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+type Result<T> = std::result::Result<T, ConfigFileError>;
+
+/// One layer of `arg name -> value` fallbacks, loaded from a TOML or JSON config file.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ConfigFile {
+    values: HashMap<String, String>,
+}
+
+impl ConfigFile {
+    /// Loads `path`, dispatching on its extension to the `toml` or `json` feature's parser.
+    pub(crate) fn load(path: &Path) -> Result<ConfigFile> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| ConfigFileError(format!("could not read config file: {}", e)))?;
+
+        let values = match path.extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "toml")]
+            Some("toml") => Self::parse_toml(&contents)?,
+            #[cfg(feature = "json")]
+            Some("json") => Self::parse_json(&contents)?,
+            Some(other) => {
+                return Err(ConfigFileError(format!(
+                    "unsupported config file extension: {}",
+                    other
+                )))
+            }
+            None => {
+                return Err(ConfigFileError(
+                    "config file has no extension to dispatch on".to_string(),
+                ))
+            }
+        };
+
+        Ok(ConfigFile { values })
+    }
+
+    #[cfg(feature = "toml")]
+    fn parse_toml(contents: &str) -> Result<HashMap<String, String>> {
+        let table: toml::Value = contents
+            .parse()
+            .map_err(|e| ConfigFileError(format!("invalid TOML config file: {}", e)))?;
+        Ok(Self::flatten_toml(table))
+    }
+
+    /// Flattens a parsed TOML document into `arg name -> value` pairs, recursing into nested
+    /// tables and joining them onto their key with `.` (e.g. `[section]` / `key = 1` becomes
+    /// `section.key` -> `"1"`) rather than mangling them into a literal `Debug`-ish string.
+    #[cfg(feature = "toml")]
+    fn flatten_toml(value: toml::Value) -> HashMap<String, String> {
+        let mut out = HashMap::new();
+        if let toml::Value::Table(table) = value {
+            Self::flatten_table(String::new(), table, &mut out);
+        }
+        out
+    }
+
+    #[cfg(feature = "toml")]
+    fn flatten_table(prefix: String, table: toml::value::Table, out: &mut HashMap<String, String>) {
+        for (key, val) in table {
+            let full_key = if prefix.is_empty() {
+                key
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            match val {
+                toml::Value::Table(nested) => Self::flatten_table(full_key, nested, out),
+                other => {
+                    out.insert(full_key, Self::scalar_to_string(other));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn parse_json(contents: &str) -> Result<HashMap<String, String>> {
+        let value: serde_json::Value = serde_json::from_str(contents)
+            .map_err(|e| ConfigFileError(format!("invalid JSON config file: {}", e)))?;
+        let mut out = HashMap::new();
+        if let serde_json::Value::Object(map) = value {
+            Self::flatten_json_object(String::new(), map, &mut out);
+        }
+        Ok(out)
+    }
+
+    /// Same flattening/scalar-rendering approach as [`Self::flatten_toml`]/[`Self::scalar_to_string`],
+    /// for JSON's value model.
+    #[cfg(feature = "json")]
+    fn flatten_json_object(
+        prefix: String,
+        map: serde_json::Map<String, serde_json::Value>,
+        out: &mut HashMap<String, String>,
+    ) {
+        for (key, val) in map {
+            let full_key = if prefix.is_empty() {
+                key
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            match val {
+                serde_json::Value::Object(nested) => Self::flatten_json_object(full_key, nested, out),
+                other => {
+                    out.insert(full_key, Self::json_scalar_to_string(other));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn json_scalar_to_string(value: serde_json::Value) -> String {
+        match value {
+            serde_json::Value::String(s) => s,
+            serde_json::Value::Array(values) => values
+                .into_iter()
+                .map(Self::json_scalar_to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            serde_json::Value::Null => String::new(),
+            other => other.to_string(),
+        }
+    }
+
+    /// Renders a non-table TOML value as the string a CLI arg's value would have been. Strings
+    /// come through as-is (the `toml` crate has already unescaped them, so no quote-trimming is
+    /// needed or correct — trimming one layer of `"` left escaped quotes like `va\"lue` mangled).
+    /// Arrays are joined with `,`, matching how a user would type a multi-value arg on the CLI.
+    #[cfg(feature = "toml")]
+    fn scalar_to_string(value: toml::Value) -> String {
+        match value {
+            toml::Value::String(s) => s,
+            toml::Value::Integer(i) => i.to_string(),
+            toml::Value::Float(f) => f.to_string(),
+            toml::Value::Boolean(b) => b.to_string(),
+            toml::Value::Datetime(d) => d.to_string(),
+            toml::Value::Array(values) => values
+                .into_iter()
+                .map(Self::scalar_to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            // Top-level and nested tables are flattened onto their own keys before reaching here;
+            // a table inside an array has no key of its own, so it's rendered as TOML text, the
+            // way `json_scalar_to_string` renders an object inside an array as JSON text.
+            toml::Value::Table(table) => toml::Value::Table(table).to_string(),
+        }
+    }
+
+    /// Looks up the fallback value for `arg_name`, the way the parser would once an arg wasn't
+    /// found on the command line or in the environment.
+    pub(crate) fn get(&self, arg_name: &str) -> Option<&str> {
+        self.values.get(arg_name).map(String::as_str)
+    }
+}
+
+#[cfg(all(test, feature = "toml"))]
+mod toml_test {
+    use super::ConfigFile;
+    use std::collections::HashMap;
+
+    fn flatten(toml: &str) -> HashMap<String, String> {
+        ConfigFile::flatten_toml(toml.parse().unwrap())
+    }
+
+    #[test]
+    fn flattens_nested_tables_onto_dotted_keys() {
+        let values = flatten("[section]\nkey = 1\n[section.nested]\nother = \"x\"\n");
+        assert_eq!(values.get("section.key").map(String::as_str), Some("1"));
+        assert_eq!(
+            values.get("section.nested.other").map(String::as_str),
+            Some("x")
+        );
+    }
+
+    #[test]
+    fn joins_arrays_with_commas() {
+        let values = flatten("names = [\"a\", \"b\", \"c\"]\n");
+        assert_eq!(values.get("names").map(String::as_str), Some("a,b,c"));
+    }
+
+    #[test]
+    fn joins_numeric_arrays_with_commas_too() {
+        // Non-string scalars inside an array still join with `,`, via the same Array arm as the
+        // string case above rather than the `unreachable!` Table arm.
+        let values = flatten("nums = [1, 2, 3]\n");
+        assert_eq!(values.get("nums").map(String::as_str), Some("1,2,3"));
+    }
+
+    #[test]
+    fn preserves_an_escaped_quote_in_a_string_value() {
+        // The bug the chunk1-3 fix replaced: trimming one layer of leading/trailing `"` off
+        // `val.to_string()` left the backslash in `va\"lue` instead of the real `va"lue`.
+        let values = flatten("key = \"va\\\"lue\"\n");
+        assert_eq!(values.get("key").map(String::as_str), Some("va\"lue"));
+    }
+
+    #[test]
+    fn get_reads_back_a_loaded_value() {
+        let config = ConfigFile {
+            values: flatten("key = \"value\"\n"),
+        };
+        assert_eq!(config.get("key"), Some("value"));
+        assert_eq!(config.get("missing"), None);
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod json_test {
+    use super::ConfigFile;
+    use std::collections::HashMap;
+
+    fn flatten(json: &str) -> HashMap<String, String> {
+        let mut out = HashMap::new();
+        if let serde_json::Value::Object(map) = serde_json::from_str(json).unwrap() {
+            ConfigFile::flatten_json_object(String::new(), map, &mut out);
+        }
+        out
+    }
+
+    #[test]
+    fn flattens_nested_objects_onto_dotted_keys() {
+        let values = flatten(r#"{"section": {"key": 1, "nested": {"other": "x"}}}"#);
+        assert_eq!(values.get("section.key").map(String::as_str), Some("1"));
+        assert_eq!(
+            values.get("section.nested.other").map(String::as_str),
+            Some("x")
+        );
+    }
+
+    #[test]
+    fn joins_arrays_with_commas() {
+        let values = flatten(r#"{"names": ["a", "b", "c"]}"#);
+        assert_eq!(values.get("names").map(String::as_str), Some("a,b,c"));
+    }
+
+    #[test]
+    fn preserves_an_escaped_quote_in_a_string_value() {
+        let values = flatten(r#"{"key": "va\"lue"}"#);
+        assert_eq!(values.get("key").map(String::as_str), Some("va\"lue"));
+    }
+}
@@ -0,0 +1,113 @@
+//! `Arg::env`/`Arg::env_os` fallback resolution: "flag, else env var, else default".
+//!
+//! The full feature needs two things this checkout doesn't have: a place on `Arg` to record the
+//! configured variable name (`crate::build::Arg` isn't part of this tree), and a parser call site
+//! that passes an arg's command-line value (if any), its configured env var name, and its default
+//! to [`resolve`] once the arg wasn't found via the usual matching, recording the returned
+//! [`ValueSource`] on `ArgMatches` so `required`/`occurrences_of`/validators can tell a
+//! CLI-provided value from a fallback one (the parser in `crate::parse` isn't part of this tree
+//! either). This
+//! module holds the actual precedence decision, so wiring it in once those pieces land is just a
+//! call to `resolve` at the point an arg comes up empty.
+
+use std::env;
+use std::ffi::{OsStr, OsString};
+
+/// Where an argument's resolved value ultimately came from, most to least authoritative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ValueSource {
+    /// Provided directly on the command line.
+    CommandLine,
+    /// Fell back to the environment variable set via `Arg::env`/`Arg::env_os`.
+    Env,
+    /// Fell back to `Arg::default_value`/`default_value_os`.
+    Default,
+}
+
+/// The resolved value for an arg, together with where it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Resolution {
+    pub(crate) value: OsString,
+    pub(crate) source: ValueSource,
+}
+
+/// Resolves an arg's effective value given what the command line provided (if anything), the env
+/// var configured via `Arg::env`/`Arg::env_os` (if any), and the arg's default (if any), in that
+/// precedence order. Returns `None` when none of the three produced a value, i.e. the arg is
+/// simply absent (and, for a required arg, that's what should trigger
+/// `ErrorKind::MissingRequiredArgument`).
+pub(crate) fn resolve(
+    cli_value: Option<OsString>,
+    env_var: Option<&OsStr>,
+    default_value: Option<&OsStr>,
+) -> Option<Resolution> {
+    if let Some(value) = cli_value {
+        return Some(Resolution {
+            value,
+            source: ValueSource::CommandLine,
+        });
+    }
+
+    if let Some(name) = env_var {
+        if let Some(value) = lookup(name) {
+            return Some(Resolution {
+                value,
+                source: ValueSource::Env,
+            });
+        }
+    }
+
+    default_value.map(|value| Resolution {
+        value: value.to_os_string(),
+        source: ValueSource::Default,
+    })
+}
+
+/// Looks up `name` in the environment. Split out from [`resolve`] only to keep the precedence
+/// logic above free of the one line that actually touches the environment — tests still go
+/// through `resolve` and do set real process env vars (see `env_wins_over_default_when_cli_is_absent`
+/// below) to cover that code path honestly rather than mocking it away.
+fn lookup(name: &OsStr) -> Option<OsString> {
+    env::var_os(name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn command_line_wins_over_everything() {
+        let resolved = resolve(
+            Some(OsString::from("cli")),
+            Some(OsStr::new("DOES_NOT_MATTER")),
+            Some(OsStr::new("default")),
+        )
+        .unwrap();
+        assert_eq!(resolved.value, OsString::from("cli"));
+        assert_eq!(resolved.source, ValueSource::CommandLine);
+    }
+
+    #[test]
+    fn env_wins_over_default_when_cli_is_absent() {
+        let name = "CLAP_ENV_FALLBACK_TEST_VAR";
+        env::set_var(name, "from-env");
+        let resolved = resolve(None, Some(OsStr::new(name)), Some(OsStr::new("default"))).unwrap();
+        env::remove_var(name);
+        assert_eq!(resolved.value, OsString::from("from-env"));
+        assert_eq!(resolved.source, ValueSource::Env);
+    }
+
+    #[test]
+    fn default_is_used_when_cli_and_env_are_absent() {
+        let name = "CLAP_ENV_FALLBACK_TEST_VAR_UNSET";
+        env::remove_var(name);
+        let resolved = resolve(None, Some(OsStr::new(name)), Some(OsStr::new("default"))).unwrap();
+        assert_eq!(resolved.value, OsString::from("default"));
+        assert_eq!(resolved.source, ValueSource::Default);
+    }
+
+    #[test]
+    fn none_when_nothing_resolves() {
+        assert!(resolve(None, None, None).is_none());
+    }
+}
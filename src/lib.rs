@@ -342,6 +342,20 @@
 //! features = [ "suggestions", "color" ]
 //! ```
 //!
+//! #### `no_std` / `alloc`
+//!
+//! `clap` can be built against `alloc` instead of `std` for embedded and WASM targets that parse
+//! a provided `&[&str]` argument slice rather than reading `std::env::args()`. Environment-based
+//! features (`Arg::env`, `NO_COLOR`/`CLICOLOR*` detection, terminal-width detection) and anything
+//! that writes to `std::io` aren't available in this mode.
+//!
+//! ```toml
+//! [dependencies.clap]
+//! version = "~2.27.0"
+//! default-features = false
+//! features = ["alloc"]
+//! ```
+//!
 //! #### Opt-in features
 //!
 //! * **"yaml"**: Enables building CLIs from YAML documents. (builds dependency `yaml-rust`)
@@ -434,6 +448,7 @@
 
 #![crate_type = "lib"]
 #![doc(html_root_url = "https://docs.rs/clap/3.0.0-beta.1")]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(
     missing_docs,
     missing_debug_implementations,
@@ -444,8 +459,11 @@
     trivial_numeric_casts
 )]
 
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+compile_error!("`clap` requires the `std` feature, or `alloc` for `#![no_std]` targets");
+
 #[cfg(not(feature = "std"))]
-compile_error!("`std` feature is currently required to build `clap`");
+extern crate alloc;
 
 pub use crate::build::{App, AppSettings, Arg, ArgGroup, ArgSettings};
 pub use crate::derive::{Clap, FromArgMatches, IntoApp, Subcommand};
@@ -469,7 +487,11 @@ pub mod macros;
 
 pub mod derive;
 
+pub mod completions;
+
 mod build;
+mod config_file;
+mod env_fallback;
 mod mkeymap;
 mod output;
 mod parse;
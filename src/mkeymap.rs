@@ -0,0 +1,35 @@
+//! Key -> arg index used to look an arg up by name, short flag, or long flag in better than
+//! linear time instead of scanning the whole arg list, built once when `App` is finalized.
+//! `crate::build::App` isn't part of this checkout, so nothing constructs one of these yet, but
+//! the index itself doesn't depend on it.
+
+#[cfg(feature = "std")]
+use std::collections::HashMap as Map;
+
+// `std::collections::HashMap` needs `std`'s source of randomness to seed its hasher, which isn't
+// available under `alloc`-only builds; `BTreeMap` needs nothing but `alloc` and gives the same
+// `get`/`insert` shape this module uses.
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as Map;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// Maps each of an arg's keys (its name, and `-s`/`--long` if set) to its position in the arg
+/// list.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct MKeyMap {
+    keys: Map<String, usize>,
+}
+
+impl MKeyMap {
+    /// Records that `key` refers to the arg at `index`.
+    pub(crate) fn insert(&mut self, key: String, index: usize) {
+        self.keys.insert(key, index);
+    }
+
+    /// Looks up the arg index for `key`, if any arg was registered under it.
+    pub(crate) fn get(&self, key: &str) -> Option<usize> {
+        self.keys.get(key).copied()
+    }
+}
@@ -1,11 +1,22 @@
-#[cfg(all(feature = "color", not(target_os = "windows")))]
-use ansi_term::ANSIString;
+#[cfg(feature = "color")]
+use ansi_term::{ANSIString, Style};
 
-#[cfg(all(feature = "color", not(target_os = "windows")))]
+#[cfg(feature = "color")]
 use ansi_term::Colour::{Green, Red, Yellow};
 
+#[cfg(feature = "std")]
 use std::env;
+
+#[cfg(feature = "std")]
 use std::fmt;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+
+#[cfg(all(feature = "color", feature = "std", target_os = "windows"))]
+mod windows;
+
+#[cfg(all(feature = "color", feature = "std"))]
+pub(crate) mod writer;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[doc(hidden)]
@@ -32,39 +43,227 @@ pub(crate) fn is_a_tty(_: bool) -> bool {
     false
 }
 
+#[cfg(feature = "std")]
 pub(crate) fn is_term_dumb() -> bool {
     env::var("TERM").ok() == Some(String::from("dumb"))
 }
 
+// No environment to read on a `#![no_std]` target, so there's no `TERM=dumb` to detect.
+#[cfg(not(feature = "std"))]
+pub(crate) fn is_term_dumb() -> bool {
+    false
+}
+
+/// The terminal width the `wrap_help` feature wraps help text to, or `None` to fall back to the
+/// hard-coded 120-column default. Queries the real console width via `term_size`, which (like
+/// `is_a_tty`) depends on `std::io`, so it isn't available on `#![no_std]` targets.
+#[cfg(all(feature = "wrap_help", feature = "std"))]
+pub(crate) fn terminal_width() -> Option<usize> {
+    term_size::dimensions_stdout().map(|(w, _)| w)
+}
+
+// No console to query on a `#![no_std]` target; callers fall back to the fixed-width default.
+#[cfg(all(feature = "wrap_help", not(feature = "std")))]
+pub(crate) fn terminal_width() -> Option<usize> {
+    None
+}
+
+/// The set of styles used to paint [`Format::Error`], [`Format::Warning`], and [`Format::Good`]
+/// messages. Defaults to the classic bold-red/yellow/green combination, but accepts any
+/// `ansi_term::Style`, including the 256-color (`Colour::Fixed`) and truecolor (`Colour::RGB`)
+/// variants, so applications can supply their own theme.
+#[cfg(feature = "color")]
+#[derive(Debug, Clone)]
+pub(crate) struct Theme {
+    pub(crate) error: Style,
+    pub(crate) warning: Style,
+    pub(crate) good: Style,
+}
+
+#[cfg(feature = "color")]
+impl Theme {
+    pub(crate) fn error(mut self, style: Style) -> Self {
+        self.error = style;
+        self
+    }
+
+    pub(crate) fn warning(mut self, style: Style) -> Self {
+        self.warning = style;
+        self
+    }
+
+    pub(crate) fn good(mut self, style: Style) -> Self {
+        self.good = style;
+        self
+    }
+}
+
+#[cfg(feature = "color")]
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            error: Red.bold(),
+            warning: Yellow.normal(),
+            good: Green.normal(),
+        }
+    }
+}
+
 pub(crate) struct ColorizerOption {
     pub(crate) use_stderr: bool,
     pub(crate) when: ColorWhen,
+    #[cfg(feature = "color")]
+    pub(crate) theme: Theme,
 }
 
 pub(crate) struct Colorizer {
     when: ColorWhen,
+    #[cfg(feature = "color")]
+    theme: Theme,
+    #[cfg(feature = "color")]
+    is_a_tty: bool,
+    #[cfg(feature = "color")]
+    use_stderr: bool,
 }
 
+#[cfg(feature = "color")]
 macro_rules! color {
-    ($_self:ident, $c:ident, $m:expr) => {
+    ($_self:ident, $c:ident, $style_field:ident, $m:expr) => {
+        match $_self.when {
+            ColorWhen::Never => Format::None($m, $_self.use_stderr),
+            ColorWhen::Auto | ColorWhen::Always => {
+                Format::$c($m, $_self.theme.$style_field, $_self.use_stderr)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "color"))]
+macro_rules! color {
+    ($_self:ident, $c:ident, $style_field:ident, $m:expr) => {
         match $_self.when {
-            ColorWhen::Auto => Format::$c($m),
-            ColorWhen::Always => Format::$c($m),
             ColorWhen::Never => Format::None($m),
+            ColorWhen::Auto | ColorWhen::Always => Format::$c($m),
         }
     };
 }
 
+// Returns whether an env var is "set and truthy" in the `CLICOLOR_FORCE`/`CLICOLOR` sense, i.e.
+// present and not equal to "0".
+#[cfg(feature = "std")]
+fn is_non_zero(name: &str) -> bool {
+    match env::var(name) {
+        Ok(ref val) => val != "0",
+        Err(_) => false,
+    }
+}
+
 impl Colorizer {
     pub(crate) fn new(option: &ColorizerOption) -> Colorizer {
-        let is_a_tty = is_a_tty(option.use_stderr);
-        let is_term_dumb = is_term_dumb();
+        let when = match option.when {
+            // Only consult NO_COLOR/CLICOLOR*/CLICOLOR_FORCE when the application didn't
+            // explicitly request `Always`/`Never`; an explicit choice always wins over those env
+            // vars. It does NOT win over being attached to a real terminal, though: piping
+            // `--color=always` output into a file or a non-pty `less` still shouldn't emit escape
+            // sequences, so the TTY/`TERM=dumb` check applies to explicit choices too, same as it
+            // did before NO_COLOR/CLICOLOR* support was added.
+            ColorWhen::Auto => Colorizer::env_when(option.use_stderr),
+            explicit => Colorizer::downgrade_unless_tty(explicit, option.use_stderr),
+        };
         Colorizer {
-            when: if is_a_tty && !is_term_dumb {
-                option.when
-            } else {
-                ColorWhen::Never
-            },
+            when,
+            #[cfg(feature = "color")]
+            theme: option.theme.clone(),
+            #[cfg(feature = "color")]
+            is_a_tty: is_a_tty(option.use_stderr),
+            #[cfg(feature = "color")]
+            use_stderr: option.use_stderr,
+        }
+    }
+
+    /// Wraps `dest` in a [`writer::Writer`] that strips ANSI escapes unless the stdout/stderr
+    /// stream `self` was built for (per [`ColorizerOption::use_stderr`]) is a real terminal; `dest`
+    /// itself isn't inspected. Help/error text printed via `self.error()`/`self.good()`/etc. so
+    /// stays clean when piped or redirected even though `self.when` may be `Always`. Needs `std` itself, same as
+    /// `writer::Writer` (there's no `core`/`alloc`-only equivalent of `std::io::Write`).
+    #[cfg(all(feature = "color", feature = "std"))]
+    pub(crate) fn writer<W: std::io::Write>(&self, dest: W) -> writer::Writer<W> {
+        writer::Writer::new(dest, self.is_a_tty)
+    }
+
+    /// Writes `fmt` to `dest` through [`Colorizer::writer`], so callers (like
+    /// [`Colorizer::print`], or a future `App::write_help`/`write_error` once `crate::build` and
+    /// the parser in `crate::parse` exist) get ANSI-stripped output for free instead of having to remember to
+    /// wrap their destination themselves.
+    #[cfg(all(feature = "color", feature = "std"))]
+    pub(crate) fn print_to<T: AsRef<str>, W: std::io::Write>(
+        &self,
+        value: &Format<T>,
+        dest: W,
+    ) -> std::io::Result<()> {
+        let mut dest = self.writer(dest);
+        write!(dest, "{}", value)
+    }
+
+    /// Writes `value` to the real stdout/stderr handle `self` was built for, per
+    /// [`ColorizerOption::use_stderr`], through [`Colorizer::print_to`].
+    ///
+    /// No caller yet: the help/error-printing call sites this is for live in `crate::build` and
+    /// the parser in `crate::parse`, neither of which is part of this checkout. Flagging now so it isn't
+    /// forgotten — once those land and wire this in, this note can go; until then a `-D warnings`
+    /// build will trip `dead_code` on it.
+    #[cfg(all(feature = "color", feature = "std"))]
+    pub(crate) fn print<T: AsRef<str>>(&self, value: &Format<T>) -> std::io::Result<()> {
+        if self.use_stderr {
+            self.print_to(value, std::io::stderr())
+        } else {
+            self.print_to(value, std::io::stdout())
+        }
+    }
+
+    // Implements the de-facto `NO_COLOR`/`CLICOLOR`/`CLICOLOR_FORCE` conventions on top of the
+    // existing TTY/`TERM=dumb` gating. Precedence, highest first:
+    //   1. `CLICOLOR_FORCE` (non-zero) forces color on regardless of TTY state
+    //   2. `NO_COLOR` (any value) forces color off
+    //   3. `CLICOLOR=0` forces color off
+    //   4. otherwise fall back to the TTY + `TERM=dumb` check
+    //
+    // There's no environment to read on a `#![no_std]` target, so color there always falls back
+    // to whatever the TTY/`TERM=dumb` check (itself a no-op without `std`) resolves to: `Never`.
+    #[cfg(feature = "std")]
+    fn env_when(use_stderr: bool) -> ColorWhen {
+        if is_non_zero("CLICOLOR_FORCE") {
+            return ColorWhen::Always;
+        }
+        if env::var_os("NO_COLOR").is_some() {
+            return ColorWhen::Never;
+        }
+        if env::var("CLICOLOR").ok().as_deref() == Some("0") {
+            return ColorWhen::Never;
+        }
+
+        let is_a_tty = is_a_tty(use_stderr);
+        let is_term_dumb = is_term_dumb();
+        if is_a_tty && !is_term_dumb {
+            ColorWhen::Auto
+        } else {
+            ColorWhen::Never
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn env_when(_use_stderr: bool) -> ColorWhen {
+        ColorWhen::Never
+    }
+
+    // Gates an explicit `Always`/`Never` behind the same TTY/`TERM=dumb` check `env_when` applies
+    // to `Auto`, so an app-forced color choice still doesn't leak escape sequences into a pipe or
+    // a dumb terminal.
+    fn downgrade_unless_tty(explicit: ColorWhen, use_stderr: bool) -> ColorWhen {
+        if is_a_tty(use_stderr) && !is_term_dumb() {
+            explicit
+        } else {
+            ColorWhen::Never
         }
     }
 
@@ -73,7 +272,7 @@ impl Colorizer {
         T: fmt::Display + AsRef<str>,
     {
         debugln!("Colorizer::good;");
-        color!(self, Good, msg)
+        color!(self, Good, good, msg)
     }
 
     pub(crate) fn warning<T>(&self, msg: T) -> Format<T>
@@ -81,7 +280,7 @@ impl Colorizer {
         T: fmt::Display + AsRef<str>,
     {
         debugln!("Colorizer::warning;");
-        color!(self, Warning, msg)
+        color!(self, Warning, warning, msg)
     }
 
     pub(crate) fn error<T>(&self, msg: T) -> Format<T>
@@ -89,9 +288,19 @@ impl Colorizer {
         T: fmt::Display + AsRef<str>,
     {
         debugln!("Colorizer::error;");
-        color!(self, Error, msg)
+        color!(self, Error, error, msg)
+    }
+
+    #[cfg(feature = "color")]
+    pub(crate) fn none<T>(&self, msg: T) -> Format<T>
+    where
+        T: fmt::Display + AsRef<str>,
+    {
+        debugln!("Colorizer::none;");
+        Format::None(msg, self.use_stderr)
     }
 
+    #[cfg(not(feature = "color"))]
     pub(crate) fn none<T>(&self, msg: T) -> Format<T>
     where
         T: fmt::Display + AsRef<str>,
@@ -106,13 +315,34 @@ impl Default for Colorizer {
         Colorizer::new(&ColorizerOption {
             use_stderr: true,
             when: ColorWhen::Auto,
+            #[cfg(feature = "color")]
+            theme: Theme::default(),
         })
     }
 }
 
+/// Defines the category of a piece of output text. The actual color used for each variant is
+/// resolved at `Colorizer` construction time from its [`Theme`] (or disabled entirely via
+/// [`Format::None`]), defaulting to Error=Red, Warning=Yellow, and Good=Green. The trailing `bool`
+/// records whether this text is destined for stderr (`true`) or stdout (`false`) — the Windows
+/// backend needs that to know which console handle to paint.
+#[derive(Debug)]
+#[cfg(feature = "color")]
+pub(crate) enum Format<T> {
+    /// Text styled as an error, defaults to bold Red
+    Error(T, Style, bool),
+    /// Text styled as a warning, defaults to Yellow
+    Warning(T, Style, bool),
+    /// Text styled as a positive/success value, defaults to Green
+    Good(T, Style, bool),
+    /// Unstyled text
+    None(T, bool),
+}
+
 /// Defines styles for different types of error messages. Defaults to Error=Red, Warning=Yellow,
 /// and Good=Green
 #[derive(Debug)]
+#[cfg(not(feature = "color"))]
 pub(crate) enum Format<T> {
     /// Defines the style used for errors, defaults to Red
     Error(T),
@@ -124,19 +354,31 @@ pub(crate) enum Format<T> {
     None(T),
 }
 
-#[cfg(all(feature = "color", not(target_os = "windows")))]
+#[cfg(feature = "color")]
 impl<T: AsRef<str>> Format<T> {
     fn format(&self) -> ANSIString {
         match *self {
-            Format::Error(ref e) => Red.bold().paint(e.as_ref()),
-            Format::Warning(ref e) => Yellow.paint(e.as_ref()),
-            Format::Good(ref e) => Green.paint(e.as_ref()),
-            Format::None(ref e) => ANSIString::from(e.as_ref()),
+            Format::Error(ref e, style, _)
+            | Format::Warning(ref e, style, _)
+            | Format::Good(ref e, style, _) => style.paint(e.as_ref()),
+            Format::None(ref e, _) => ANSIString::from(e.as_ref()),
+        }
+    }
+
+    // Which stream (stderr when `true`, else stdout) this text is destined for. Used by the
+    // Windows backend to pick the right console handle instead of assuming stderr.
+    #[cfg(target_os = "windows")]
+    fn use_stderr(&self) -> bool {
+        match *self {
+            Format::Error(_, _, use_stderr)
+            | Format::Warning(_, _, use_stderr)
+            | Format::Good(_, _, use_stderr)
+            | Format::None(_, use_stderr) => use_stderr,
         }
     }
 }
 
-#[cfg(any(not(feature = "color"), target_os = "windows"))]
+#[cfg(not(feature = "color"))]
 impl<T: fmt::Display> Format<T> {
     fn format(&self) -> &T {
         match *self {
@@ -148,41 +390,137 @@ impl<T: fmt::Display> Format<T> {
     }
 }
 
-#[cfg(all(feature = "color", not(target_os = "windows")))]
+// The dedicated Windows console backend (below) needs `std` for its `winapi`/raw-handle plumbing,
+// so a `#![no_std]`-with-`alloc` build on Windows falls back to the plain ANSI-escape formatting
+// every other non-Windows `std` build already uses, same as if it weren't Windows at all.
+#[cfg(all(
+    feature = "color",
+    any(not(target_os = "windows"), not(feature = "std"))
+))]
 impl<T: AsRef<str>> fmt::Display for Format<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", &self.format())
     }
 }
 
-#[cfg(any(not(feature = "color"), target_os = "windows"))]
+#[cfg(not(feature = "color"))]
 impl<T: fmt::Display> fmt::Display for Format<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", &self.format())
     }
 }
 
-#[cfg(all(test, feature = "color", not(target_os = "windows")))]
+#[cfg(all(feature = "color", feature = "std", target_os = "windows"))]
+impl<T: AsRef<str>> fmt::Display for Format<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        windows::write_colored(f, self)
+    }
+}
+
+#[cfg(all(test, feature = "color", feature = "std", not(target_os = "windows")))]
 mod test {
-    use super::Format;
+    use super::{Colorizer, ColorizerOption, ColorWhen, Format, Style, Theme};
     use ansi_term::ANSIString;
     use ansi_term::Colour::{Green, Red, Yellow};
 
     #[test]
     fn colored_output() {
-        let err = Format::Error("error");
+        let err = Format::Error("error", Red.bold(), true);
         assert_eq!(
             &*format!("{}", err),
             &*format!("{}", Red.bold().paint("error"))
         );
-        let good = Format::Good("good");
+        let good = Format::Good("good", Green.normal(), false);
         assert_eq!(&*format!("{}", good), &*format!("{}", Green.paint("good")));
-        let warn = Format::Warning("warn");
+        let warn = Format::Warning("warn", Yellow.normal(), true);
         assert_eq!(&*format!("{}", warn), &*format!("{}", Yellow.paint("warn")));
-        let none = Format::None("none");
+        let none: Format<&str> = Format::None("none", false);
         assert_eq!(
             &*format!("{}", none),
             &*format!("{}", ANSIString::from("none"))
         );
     }
+
+    #[test]
+    fn custom_theme() {
+        let custom = Style::new().fg(ansi_term::Colour::Fixed(208));
+        let warn = Format::Warning("warn", custom, true);
+        assert_eq!(&*format!("{}", warn), &*format!("{}", custom.paint("warn")));
+    }
+
+    #[test]
+    fn print_to_strips_escapes_for_non_tty_destinations() {
+        // `Colorizer::writer` had no call site: nothing actually routed a `Format` through
+        // `StripAnsi` before writing it anywhere. `print_to` is that call site, so exercise it
+        // end-to-end (with an actually-styled `Format`, so there are real escapes to strip)
+        // rather than only unit-testing `StripAnsi` in isolation.
+        let colorizer = Colorizer::new(&ColorizerOption {
+            use_stderr: false,
+            when: ColorWhen::Never,
+            theme: Theme::default(),
+        });
+        // Not a real terminal under the test harness, so `print_to` strips this back down to
+        // plain text even though the `Format` itself is colored.
+        assert!(!colorizer.is_a_tty);
+        let err = Format::Error("bad value", Red.bold(), false);
+        let mut out = Vec::new();
+        colorizer.print_to(&err, &mut out).unwrap();
+        assert_eq!(out, b"bad value");
+    }
+}
+
+#[cfg(all(test, feature = "color", feature = "std"))]
+mod env_test {
+    use super::{is_non_zero, Colorizer, ColorizerOption, ColorWhen};
+    use std::env;
+
+    // These tests mutate process-wide env vars, so they share one test function to avoid racing
+    // against each other under the default parallel test runner.
+    #[test]
+    fn color_env_precedence() {
+        env::remove_var("CLICOLOR_FORCE");
+        env::remove_var("NO_COLOR");
+        env::remove_var("CLICOLOR");
+
+        // CLICOLOR_FORCE wins even though we're not attached to a TTY in test harnesses.
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(Colorizer::env_when(false), ColorWhen::Always);
+        assert!(is_non_zero("CLICOLOR_FORCE"));
+        env::remove_var("CLICOLOR_FORCE");
+
+        // NO_COLOR disables regardless of its value.
+        env::set_var("NO_COLOR", "0");
+        assert_eq!(Colorizer::env_when(false), ColorWhen::Never);
+        env::remove_var("NO_COLOR");
+
+        // CLICOLOR=0 disables, CLICOLOR=1 falls back to the TTY/TERM=dumb check.
+        env::set_var("CLICOLOR", "0");
+        assert_eq!(Colorizer::env_when(false), ColorWhen::Never);
+        env::remove_var("CLICOLOR");
+
+        // An explicit Always/Never wins over NO_COLOR/CLICOLOR* specifically...
+        env::set_var("NO_COLOR", "1");
+        let forced = Colorizer::new(&ColorizerOption {
+            use_stderr: false,
+            when: ColorWhen::Always,
+            theme: super::Theme::default(),
+        });
+        // ...but test harnesses aren't attached to a real terminal, so it's still downgraded to
+        // Never here the same way it would be if this process were piped into a file or `less`:
+        // an explicit app choice has never been a license to emit raw escape codes at a
+        // non-terminal destination, NO_COLOR aside.
+        assert_eq!(forced.when, ColorWhen::Never);
+        env::remove_var("NO_COLOR");
+
+        // Covers the regression where an explicit Always/Never briefly skipped the TTY/
+        // `TERM=dumb` check entirely instead of just skipping the NO_COLOR/CLICOLOR* check.
+        env::remove_var("CLICOLOR_FORCE");
+        env::remove_var("TERM");
+        let forced = Colorizer::new(&ColorizerOption {
+            use_stderr: false,
+            when: ColorWhen::Always,
+            theme: super::Theme::default(),
+        });
+        assert_eq!(forced.when, ColorWhen::Never);
+    }
 }
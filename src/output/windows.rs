@@ -0,0 +1,142 @@
+// Windows console backend for `Format`. Mirrors the approach taken by `termcolor`: first try to
+// flip the console into ANSI mode (available on Windows 10+) so the escape sequences we already
+// emit on other platforms just work, and only fall back to the legacy attribute-based API when
+// that isn't supported. The legacy path only understands the basic 16-color palette, so it maps
+// each category to its nearest console attribute rather than honoring a custom `Theme`.
+
+use std::cell::Cell;
+use std::fmt;
+use std::io;
+use std::os::windows::io::{AsRawHandle, RawHandle};
+
+use winapi::shared::minwindef::DWORD;
+use winapi::um::consoleapi::{GetConsoleMode, SetConsoleMode};
+use winapi::um::wincon::{
+    GetConsoleScreenBufferInfo, SetConsoleTextAttribute, CONSOLE_SCREEN_BUFFER_INFO,
+    ENABLE_VIRTUAL_TERMINAL_PROCESSING, FOREGROUND_GREEN, FOREGROUND_INTENSITY, FOREGROUND_RED,
+};
+use winapi::um::winnt::HANDLE;
+
+use super::Format;
+
+thread_local! {
+    static VT_SUPPORTED: Cell<Option<bool>> = Cell::new(None);
+}
+
+fn handle_for(stderr: bool) -> HANDLE {
+    let raw: RawHandle = if stderr {
+        io::stderr().as_raw_handle()
+    } else {
+        io::stdout().as_raw_handle()
+    };
+    raw as HANDLE
+}
+
+// Tries to enable `ENABLE_VIRTUAL_TERMINAL_PROCESSING` on the given console handle, caching the
+// result per-thread since the mode only needs to be flipped once per process.
+fn vt_supported(stderr: bool) -> bool {
+    VT_SUPPORTED.with(|cached| {
+        if let Some(supported) = cached.get() {
+            return supported;
+        }
+        let supported = unsafe {
+            let handle = handle_for(stderr);
+            let mut mode: DWORD = 0;
+            if GetConsoleMode(handle, &mut mode) == 0 {
+                false
+            } else {
+                SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+            }
+        };
+        cached.set(Some(supported));
+        supported
+    })
+}
+
+fn legacy_attrs<T>(format: &Format<T>) -> Option<DWORD> {
+    match *format {
+        Format::Error(..) => Some(FOREGROUND_RED | FOREGROUND_INTENSITY),
+        Format::Warning(..) => Some(FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_INTENSITY),
+        Format::Good(..) => Some(FOREGROUND_GREEN | FOREGROUND_INTENSITY),
+        Format::None(..) => None,
+    }
+}
+
+fn text_of<T: AsRef<str>>(format: &Format<T>) -> &str {
+    match *format {
+        Format::Error(ref e, ..)
+        | Format::Warning(ref e, ..)
+        | Format::Good(ref e, ..)
+        | Format::None(ref e, ..) => e.as_ref(),
+    }
+}
+
+// Paints `msg` via `SetConsoleTextAttribute`, restoring the previous attributes afterward so we
+// don't bleed color into whatever gets printed next, then writes `msg` itself through `f` like
+// any other `Display` impl — never straight to the console — so formatting into a buffer (e.g.
+// `format!("{}", x)`, or a file/`String` destination) works the same as it does on every other
+// platform.
+fn write_legacy(f: &mut fmt::Formatter, stderr: bool, attrs: DWORD, msg: &str) -> fmt::Result {
+    let handle = handle_for(stderr);
+    let (have_info, info) = unsafe {
+        let mut info: CONSOLE_SCREEN_BUFFER_INFO = std::mem::zeroed();
+        let have_info = GetConsoleScreenBufferInfo(handle, &mut info) != 0;
+        (have_info, info)
+    };
+
+    unsafe {
+        SetConsoleTextAttribute(handle, attrs);
+    }
+    let result = f.write_str(msg);
+    if have_info {
+        unsafe {
+            SetConsoleTextAttribute(handle, info.wAttributes);
+        }
+    }
+    result
+}
+
+pub(super) fn write_colored<T: AsRef<str>>(
+    f: &mut fmt::Formatter,
+    format: &Format<T>,
+) -> fmt::Result {
+    let stderr = format.use_stderr();
+
+    if vt_supported(stderr) {
+        // The console now understands the same ANSI escapes we emit on other platforms, so reuse
+        // the exact same `Theme`-driven styling.
+        return write!(f, "{}", format.format());
+    }
+
+    match legacy_attrs(format) {
+        Some(attrs) => write_legacy(f, stderr, attrs, text_of(format)),
+        None => f.write_str(text_of(format)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ansi_term::Colour::{Green, Red};
+
+    // `legacy_attrs`/`text_of` drive the fallback path for consoles that don't understand ANSI,
+    // so they need to key off the `Format` variant (category) rather than the `Theme`-driven
+    // `Style` or the destination stream — otherwise a custom `Theme` would have no effect on the
+    // VT-capable path but silently change which palette entry the legacy path picks, and a
+    // stdout-destined `Format` could end up painted as if it were stderr (the chunk0-1 bug).
+    #[test]
+    fn legacy_attrs_ignore_style_and_stream() {
+        let on_stderr = Format::Error("e", Red.bold(), true);
+        let on_stdout = Format::Error("e", Green.normal(), false);
+        assert_eq!(legacy_attrs(&on_stderr), legacy_attrs(&on_stdout));
+        assert_eq!(text_of(&on_stderr), text_of(&on_stdout));
+    }
+
+    #[test]
+    fn use_stderr_reflects_the_stream_the_format_was_built_for() {
+        let err = Format::Warning("w", Red.bold(), true);
+        let out = Format::None("n", false);
+        assert!(err.use_stderr());
+        assert!(!out.use_stderr());
+    }
+}
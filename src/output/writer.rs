@@ -0,0 +1,106 @@
+// A small `io::Write` wrapper, modeled on `anstream`, that strips ANSI SGR escape sequences when
+// the destination isn't a real terminal. `Colorizer` picks `ColorWhen` up front (including
+// `--color=always`), so once an application forces coloring on, the raw escapes would otherwise
+// leak straight through to a pipe or a redirected file; wrapping the destination writer here
+// keeps that decision out of every call site that prints help/error text.
+
+use std::io::{self, Write};
+
+/// Strips ANSI CSI (`ESC [ ... final-byte`) sequences from anything written through it.
+pub(crate) struct StripAnsi<W> {
+    inner: W,
+    // Whether a CSI sequence begun in a previous `write` call hasn't seen its final byte yet.
+    in_escape: bool,
+}
+
+impl<W: Write> StripAnsi<W> {
+    fn new(inner: W) -> Self {
+        StripAnsi {
+            inner,
+            in_escape: false,
+        }
+    }
+}
+
+impl<W: Write> Write for StripAnsi<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut visible = Vec::with_capacity(buf.len());
+        let mut bytes = buf.iter().copied().peekable();
+        while let Some(b) = bytes.next() {
+            if self.in_escape {
+                // CSI sequences end at the first byte in the 0x40..=0x7E "final byte" range.
+                if (0x40..=0x7E).contains(&b) {
+                    self.in_escape = false;
+                }
+                continue;
+            }
+            if b == 0x1b && bytes.peek() == Some(&b'[') {
+                bytes.next();
+                self.in_escape = true;
+                continue;
+            }
+            visible.push(b);
+        }
+        self.inner.write_all(&visible)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Chooses the right writer for a destination: a raw passthrough for real terminals, or a
+/// [`StripAnsi`] wrapper otherwise, so colored help/error text stays clean when piped or
+/// redirected regardless of how `ColorWhen` was resolved.
+pub(crate) enum Writer<W> {
+    Raw(W),
+    Stripped(StripAnsi<W>),
+}
+
+impl<W: Write> Writer<W> {
+    pub(crate) fn new(inner: W, is_a_tty: bool) -> Self {
+        if is_a_tty {
+            Writer::Raw(inner)
+        } else {
+            Writer::Stripped(StripAnsi::new(inner))
+        }
+    }
+}
+
+impl<W: Write> Write for Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match *self {
+            Writer::Raw(ref mut w) => w.write(buf),
+            Writer::Stripped(ref mut w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match *self {
+            Writer::Raw(ref mut w) => w.flush(),
+            Writer::Stripped(ref mut w) => w.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StripAnsi;
+    use std::io::Write;
+
+    #[test]
+    fn strips_sgr_sequences() {
+        let mut out = StripAnsi::new(Vec::new());
+        write!(out, "\x1b[1;31merror\x1b[0m: bad value").unwrap();
+        assert_eq!(out.inner, b"error: bad value");
+    }
+
+    #[test]
+    fn splits_escape_across_writes() {
+        let mut out = StripAnsi::new(Vec::new());
+        out.write_all(b"\x1b[1;31").unwrap();
+        out.write_all(b"merror\x1b[0m").unwrap();
+        assert_eq!(out.inner, b"error");
+    }
+}
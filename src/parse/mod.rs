@@ -0,0 +1,3 @@
+// `ArgMatches`, `OsValues`, `SubCommand`, and `Values` (re-exported from the crate root alongside
+// `errors`) aren't part of this checkout, so only `errors` is wired up here.
+pub(crate) mod errors;